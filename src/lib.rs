@@ -50,9 +50,45 @@ use num_traits::AsPrimitive;
 use num_traits::CheckedSub;
 use num_traits::Unsigned;
 use num_traits::Signed;
+use num_traits::PrimInt;
+use num_traits::NumCast;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DetentMode {
+    Full,
+    Half,
+    Quarter,
+}
+
+impl DetentMode {
+    fn encoder_div(self) -> u8 {
+        match self {
+            DetentMode::Full => 4,
+            DetentMode::Half => 1,
+            DetentMode::Quarter => 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Encoding {
+    Gray,
+    Binary,
+}
+
+fn gray_to_binary<Pos: PrimInt>(gray: Pos) -> Pos {
+    let bits = (core::mem::size_of::<Pos>() * 8) as u32;
+    let mut binary = gray;
+    let mut shift = 1u32;
+    while shift < bits {
+        binary = binary ^ binary.unsigned_shr(shift);
+        shift *= 2;
+    }
+    binary
+}
 
 pub struct RotaryEncoder<Pos, Tick, Delta> where
-    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + Default,
+    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + PrimInt + Default,
     Tick: Unsigned + Bounded + Copy + PartialOrd + CheckedSub + Default,
     Delta: Signed + Copy + AsPrimitive<Pos>,
 {
@@ -61,16 +97,24 @@ pub struct RotaryEncoder<Pos, Tick, Delta> where
     last_real_raw_position: Pos,
     reset_timeout: Tick,
     div: Delta,
+    encoding: Encoding,
+    reversed: bool,
 }
 
 impl<Pos, Tick, Delta> RotaryEncoder<Pos, Tick, Delta> where
-    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + Default,
+    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + PrimInt + Default,
     Tick: Unsigned + Bounded + Copy + PartialOrd + CheckedSub + Default,
     Delta: Signed + Copy + AsPrimitive<Pos>,
 {
     pub fn new(div: Delta, reset_timeout: Tick) -> Self {
+        Self::with_encoding(div, reset_timeout, Encoding::Binary)
+    }
+
+    pub fn with_encoding(div: Delta, reset_timeout: Tick, encoding: Encoding) -> Self {
         RotaryEncoder {
             div,
+            encoding,
+            reversed: false,
             last_active: Default::default(),
             last_effective_raw_position: Default::default(),
             last_real_raw_position: Default::default(),
@@ -78,8 +122,25 @@ impl<Pos, Tick, Delta> RotaryEncoder<Pos, Tick, Delta> where
         }
     }
 
+    pub fn with_detent_mode(mode: DetentMode, reset_timeout: Tick) -> Self where
+        Delta: NumCast,
+    {
+        let div = Delta::from(mode.encoder_div()).expect("detent mode divisor fits into Delta");
+        Self::new(div, reset_timeout)
+    }
+
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
     pub fn get_delta(&mut self, raw_position: Pos, ts: Tick) -> Delta where
     {
+        let raw_position = match self.encoding {
+            Encoding::Gray => gray_to_binary(raw_position),
+            Encoding::Binary => raw_position,
+        };
+
         if (self.last_active + self.reset_timeout).checked_sub(&ts) == None {
             self.last_effective_raw_position = self.last_real_raw_position;
         }
@@ -94,7 +155,186 @@ impl<Pos, Tick, Delta> RotaryEncoder<Pos, Tick, Delta> where
             self.last_active = ts;
             self.last_real_raw_position = raw_position;
         }
-        divisions
+        if self.reversed {
+            -divisions
+        } else {
+            divisions
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OutOfRangePolicy {
+    Clamp,
+    Rollover,
+}
+
+pub struct BoundedEncoder<Pos, Tick, Delta> where
+    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + PrimInt + Signed + Default + 'static,
+    Tick: Unsigned + Bounded + Copy + PartialOrd + CheckedSub + Default,
+    Delta: Signed + Copy + AsPrimitive<Pos>,
+{
+    encoder: RotaryEncoder<Pos, Tick, Delta>,
+    position: Pos,
+    min: Pos,
+    max: Pos,
+    policy: OutOfRangePolicy,
+}
+
+impl<Pos, Tick, Delta> BoundedEncoder<Pos, Tick, Delta> where
+    Pos: Num + WrappingAdd + WrappingSub + Bounded + Copy + PartialOrd + AsPrimitive<Delta> + PrimInt + Signed + Default + 'static,
+    Tick: Unsigned + Bounded + Copy + PartialOrd + CheckedSub + Default,
+    Delta: Signed + Copy + AsPrimitive<Pos>,
+{
+    pub fn new(div: Delta, reset_timeout: Tick, min: Pos, max: Pos, policy: OutOfRangePolicy) -> Self {
+        BoundedEncoder {
+            encoder: RotaryEncoder::new(div, reset_timeout),
+            position: min,
+            min,
+            max,
+            policy,
+        }
+    }
+
+    pub fn position(&self) -> Pos {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: Pos) {
+        self.position = position;
+    }
+
+    pub fn set_range(&mut self, min: Pos, max: Pos) {
+        self.min = min;
+        self.max = max;
+    }
+
+    pub fn get_position(&mut self, raw_position: Pos, ts: Tick) -> Pos {
+        let divisions = self.encoder.get_delta(raw_position, ts);
+        self.step(divisions);
+        self.position
+    }
+
+    fn step(&mut self, divisions: Delta) {
+        let mut remaining = divisions;
+        while !remaining.is_zero() {
+            if remaining.is_negative() {
+                self.position = self.advance(false);
+                remaining = remaining + Delta::one();
+            } else {
+                self.position = self.advance(true);
+                remaining = remaining - Delta::one();
+            }
+        }
+    }
+
+    fn advance(&self, up: bool) -> Pos {
+        if up {
+            if self.position == self.max {
+                match self.policy {
+                    OutOfRangePolicy::Clamp => self.max,
+                    OutOfRangePolicy::Rollover => self.min,
+                }
+            } else {
+                self.position.wrapping_add(&Pos::one())
+            }
+        } else if self.position == self.min {
+            match self.policy {
+                OutOfRangePolicy::Clamp => self.min,
+                OutOfRangePolicy::Rollover => self.max,
+            }
+        } else {
+            self.position.wrapping_sub(&Pos::one())
+        }
+    }
+}
+
+const QUADRATURE_TABLE: [i8; 16] = [0, -1, 1, 0, 1, 0, 0, -1, -1, 0, 0, 1, 0, 1, -1, 0];
+
+pub struct QuadratureDecoder<Pos> where
+    Pos: Num + WrappingAdd + WrappingSub + Copy,
+{
+    position: Pos,
+    prev: u8,
+    reversed: bool,
+    detent_mode: DetentMode,
+    armed: bool,
+    last_rest: u8,
+    session: i8,
+}
+
+impl<Pos> QuadratureDecoder<Pos> where
+    Pos: Num + WrappingAdd + WrappingSub + Copy,
+{
+    pub fn new() -> Self {
+        QuadratureDecoder {
+            position: Pos::zero(),
+            prev: 0,
+            reversed: false,
+            detent_mode: DetentMode::Full,
+            armed: false,
+            last_rest: 0,
+            session: 0,
+        }
+    }
+
+    pub fn reversed(mut self, reversed: bool) -> Self {
+        self.reversed = reversed;
+        self
+    }
+
+    pub fn detent_mode(mut self, detent_mode: DetentMode) -> Self {
+        self.detent_mode = detent_mode;
+        self
+    }
+
+    pub fn position(&self) -> Pos {
+        self.position
+    }
+
+    pub fn update(&mut self, a: bool, b: bool) {
+        let curr = ((a as u8) << 1) | (b as u8);
+        let invert = if self.reversed { 0b10 } else { 0 };
+        let idx = ((self.prev ^ invert) << 2) | (curr ^ invert);
+        let delta = QUADRATURE_TABLE[idx as usize];
+        self.prev = curr;
+
+        match self.detent_mode {
+            DetentMode::Full | DetentMode::Quarter => match delta {
+                1 => self.position = self.position.wrapping_add(&Pos::one()),
+                -1 => self.position = self.position.wrapping_sub(&Pos::one()),
+                _ => {}
+            },
+            DetentMode::Half => self.update_half(curr, delta),
+        }
+    }
+
+    // only a transition that lands on the *other* rest state counts; bouncing back is jitter
+    fn update_half(&mut self, curr: u8, delta: i8) {
+        let is_rest = curr == 0 || curr == 3;
+        if is_rest {
+            if self.armed && curr != self.last_rest {
+                if self.session > 0 {
+                    self.position = self.position.wrapping_add(&Pos::one());
+                } else if self.session < 0 {
+                    self.position = self.position.wrapping_sub(&Pos::one());
+                }
+            }
+            self.armed = false;
+            self.session = 0;
+            self.last_rest = curr;
+        } else {
+            self.armed = true;
+            self.session += delta;
+        }
+    }
+}
+
+impl<Pos> Default for QuadratureDecoder<Pos> where
+    Pos: Num + WrappingAdd + WrappingSub + Copy,
+{
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -331,4 +571,174 @@ mod tests {
             assert_eq!(enc.get_delta((p % 256) as u8, 1), if p % 4 == 0 { -1 } else { 0 });
         }
     }
+
+    #[test]
+    fn quadrature_decoder_starts_at_zero() {
+        let dec: QuadratureDecoder<i32> = QuadratureDecoder::new();
+        assert_eq!(dec.position(), 0);
+    }
+
+    #[test]
+    fn quadrature_decoder_counts_up_on_cw_rotation() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new();
+
+        dec.update(true, false);
+        dec.update(true, true);
+        dec.update(false, true);
+        dec.update(false, false);
+
+        assert_eq!(dec.position(), 4);
+    }
+
+    #[test]
+    fn quadrature_decoder_counts_down_on_ccw_rotation() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new();
+
+        dec.update(false, true);
+        dec.update(true, true);
+        dec.update(true, false);
+        dec.update(false, false);
+
+        assert_eq!(dec.position(), -4);
+    }
+
+    #[test]
+    fn quadrature_decoder_ignores_illegal_double_transitions() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new();
+
+        dec.update(true, true);
+        assert_eq!(dec.position(), 0);
+    }
+
+    #[test]
+    fn quadrature_decoder_feeds_rotary_encoder() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new();
+        let mut enc = RotaryEncoder::new(4i32, 10u32);
+
+        dec.update(true, false);
+        dec.update(true, true);
+        dec.update(false, true);
+        dec.update(false, false);
+
+        assert_eq!(enc.get_delta(dec.position(), 1), 1);
+    }
+
+    #[test]
+    fn gray_to_binary_round_trip() {
+        assert_eq!(gray_to_binary(0u8), 0);
+        assert_eq!(gray_to_binary(1u8), 1);
+        assert_eq!(gray_to_binary(3u8), 2);
+        assert_eq!(gray_to_binary(2u8), 3);
+    }
+
+    #[test]
+    fn rotary_encoder_decodes_gray_input() {
+        let mut enc: RotaryEncoder<u8, _, _> = RotaryEncoder::with_encoding(1i8, 10u32, Encoding::Gray);
+
+        assert_eq!(enc.get_delta(0, 1), 0);
+        assert_eq!(enc.get_delta(1, 1), 1);
+        assert_eq!(enc.get_delta(3, 1), 1);
+        assert_eq!(enc.get_delta(2, 1), 1);
+    }
+
+    #[test]
+    fn rotary_encoder_binary_encoding_is_pass_through() {
+        let mut enc: RotaryEncoder<u8, _, _> = RotaryEncoder::with_encoding(1i8, 10u32, Encoding::Binary);
+
+        assert_eq!(enc.get_delta(1, 1), 1);
+    }
+
+    #[test]
+    fn bounded_encoder_accumulates_within_range() {
+        let mut enc: BoundedEncoder<i8, _, _> = BoundedEncoder::new(1i8, 10u32, 0, 9, OutOfRangePolicy::Clamp);
+
+        assert_eq!(enc.get_position(1, 1), 1);
+        assert_eq!(enc.get_position(2, 1), 2);
+    }
+
+    #[test]
+    fn bounded_encoder_clamps_at_the_endpoints() {
+        let mut enc: BoundedEncoder<i8, _, _> = BoundedEncoder::new(1i8, 10u32, 0, 3, OutOfRangePolicy::Clamp);
+
+        assert_eq!(enc.get_position(5, 1), 3);
+        assert_eq!(enc.get_position(4, 1), 2);
+    }
+
+    #[test]
+    fn bounded_encoder_rolls_over_past_the_max() {
+        let mut enc: BoundedEncoder<i8, _, _> = BoundedEncoder::new(1i8, 10u32, 0, 3, OutOfRangePolicy::Rollover);
+
+        assert_eq!(enc.get_position(3, 1), 3);
+        assert_eq!(enc.get_position(4, 1), 0);
+    }
+
+    #[test]
+    fn bounded_encoder_rolls_over_past_the_min() {
+        let mut enc: BoundedEncoder<i8, _, _> = BoundedEncoder::new(1i8, 10u32, 0, 3, OutOfRangePolicy::Rollover);
+        enc.set_position(0);
+
+        assert_eq!(enc.get_position(-1, 1), 3);
+    }
+
+    #[test]
+    fn bounded_encoder_handles_a_full_width_pos_range_without_overflow() {
+        let mut enc: BoundedEncoder<i8, _, _> = BoundedEncoder::new(1i8, 10u32, i8::MIN, i8::MAX, OutOfRangePolicy::Rollover);
+        enc.set_position(i8::MAX);
+
+        assert_eq!(enc.get_position(1, 1), i8::MIN);
+    }
+
+    #[test]
+    fn rotary_encoder_reversed_negates_divisions() {
+        let mut enc = RotaryEncoder::new(1i8, 10u32).reversed(true);
+        assert_eq!(enc.get_delta(1, 1), -1);
+    }
+
+    #[test]
+    fn quadrature_decoder_reversed_negates_direction() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new().reversed(true);
+
+        dec.update(true, false);
+        dec.update(true, true);
+        dec.update(false, true);
+        dec.update(false, false);
+
+        assert_eq!(dec.position(), -4);
+    }
+
+    #[test]
+    fn with_detent_mode_derives_div() {
+        let mut full: RotaryEncoder<i8, _, i8> = RotaryEncoder::with_detent_mode(DetentMode::Full, 10u32);
+        assert_eq!(full.get_delta(1, 1), 0);
+        assert_eq!(full.get_delta(4, 1), 1);
+
+        let mut quarter: RotaryEncoder<i8, _, i8> = RotaryEncoder::with_detent_mode(DetentMode::Quarter, 10u32);
+        assert_eq!(quarter.get_delta(1, 1), 1);
+    }
+
+    #[test]
+    fn quadrature_decoder_half_mode_counts_once_per_resting_crossing() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new().detent_mode(DetentMode::Half);
+
+        dec.update(true, false);
+        dec.update(true, true);
+        assert_eq!(dec.position(), 1);
+
+        dec.update(false, true);
+        dec.update(false, false);
+        assert_eq!(dec.position(), 2);
+    }
+
+    #[test]
+    fn quadrature_decoder_half_mode_does_not_double_count_jitter_at_rest() {
+        let mut dec: QuadratureDecoder<i32> = QuadratureDecoder::new().detent_mode(DetentMode::Half);
+
+        dec.update(true, false);
+        dec.update(true, true);
+        assert_eq!(dec.position(), 1);
+
+        dec.update(false, true);
+        dec.update(true, true);
+        assert_eq!(dec.position(), 1);
+    }
 }
\ No newline at end of file